@@ -0,0 +1,400 @@
+//! Byte-oriented LTSV parsing, for inputs that aren't guaranteed to be valid
+//! UTF-8.
+//!
+//! The grammar's `fbyte` allows any byte in `%x01-08 / %x0B / %x0C / %x0E-FF`,
+//! which the `&str`-based API in the crate root can't represent since
+//! `str::lines`/`str::split` require valid UTF-8. This module scans `&[u8]`
+//! directly instead, splitting lines on `0x0A` and fields on `0x09` exactly
+//! like [`crate::tokenize`] does for `&str`, so it stays `no_std` and can
+//! round-trip the full byte range real access logs contain.
+
+use crate::{is_fbyte, is_lbyte, ErrorKind};
+
+pub const NEWLINE: u8 = b'\n';
+pub const CARRIAGE_RETURN: u8 = b'\r';
+pub const TAB: u8 = b'\t';
+pub const SPLITTER: u8 = b':';
+
+#[derive(Debug, PartialEq)]
+pub struct ErrorBytes<'a> {
+    pub txt: &'a [u8],
+    pub kind: ErrorKind,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<'a> ErrorBytes<'a> {
+    pub fn invalid_pair(txt: &'a [u8]) -> ErrorBytes<'a> {
+        ErrorBytes {
+            txt,
+            kind: ErrorKind::InvalidPair,
+            line: 0,
+            start: 0,
+            end: 0,
+        }
+    }
+
+    pub fn invalid_label(txt: &'a [u8]) -> ErrorBytes<'a> {
+        ErrorBytes {
+            txt,
+            kind: ErrorKind::InvalidLabel,
+            line: 0,
+            start: 0,
+            end: 0,
+        }
+    }
+
+    pub fn invalid_field(txt: &'a [u8]) -> ErrorBytes<'a> {
+        ErrorBytes {
+            txt,
+            kind: ErrorKind::InvalidField,
+            line: 0,
+            start: 0,
+            end: 0,
+        }
+    }
+
+    pub fn set_line(&mut self, line: usize) {
+        self.line = line;
+    }
+
+    pub fn set_span(&mut self, start: usize, end: usize) {
+        self.start = start;
+        self.end = end;
+    }
+
+    pub fn put_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn put_span(mut self, start: usize, end: usize) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct DataBytes<'a> {
+    input: &'a [u8],
+    done: bool,
+    pub current_line: usize,
+}
+
+impl<'a> Iterator for DataBytes<'a> {
+    type Item = RecordBytes<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let line = match self.input.iter().position(|&b| b == NEWLINE) {
+            Some(pos) => {
+                let (mut line, rest) = self.input.split_at(pos);
+                if line.last() == Some(&CARRIAGE_RETURN) {
+                    line = &line[..line.len() - 1];
+                }
+                let rest = &rest[1..];
+                // a newline landing on the last byte ends the input right
+                // there; don't schedule one more (empty) line for "nothing
+                // after it", matching how `str::lines` treats a trailing
+                // terminator
+                self.done = rest.is_empty();
+                self.input = rest;
+                line
+            }
+            None => {
+                self.done = true;
+                self.input
+            }
+        };
+
+        let record = RecordBytes {
+            fields: line,
+            current_line: self.current_line,
+            current_pointer: 0,
+            done: false,
+        };
+
+        self.current_line += 1;
+
+        Some(record)
+    }
+}
+
+#[derive(Debug)]
+pub struct RecordBytes<'a> {
+    fields: &'a [u8],
+    pub current_line: usize,
+    pub current_pointer: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for RecordBytes<'a> {
+    type Item = Result<PairTokenBytes<'a>, ErrorBytes<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let pair = match self.fields.iter().position(|&b| b == TAB) {
+            Some(pos) => {
+                let (pair, rest) = self.fields.split_at(pos);
+                self.fields = &rest[1..];
+                pair
+            }
+            None => {
+                self.done = true;
+                self.fields
+            }
+        };
+
+        let start = self.current_pointer;
+        let end = start + pair.len();
+        self.current_pointer = end + 1;
+
+        match pair.iter().position(|&b| b == SPLITTER) {
+            Some(pos) => {
+                let (label, field) = (&pair[..pos], &pair[pos + 1..]);
+                let pair = PairTokenBytes {
+                    label,
+                    field,
+                    line: self.current_line,
+                    start,
+                    end,
+                };
+
+                if let Err(e) = pair.validate() {
+                    return Some(Err(e));
+                };
+
+                Some(Ok(pair))
+            }
+            None => Some(Err(ErrorBytes::invalid_pair(pair)
+                .put_line(self.current_line)
+                .put_span(start, end))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct PairTokenBytes<'a> {
+    pub label: &'a [u8],
+    pub field: &'a [u8],
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl<'a> PairTokenBytes<'a> {
+    pub fn new(label: &'a [u8], field: &'a [u8]) -> PairTokenBytes<'a> {
+        PairTokenBytes {
+            label,
+            field,
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ErrorBytes<'a>> {
+        self.validate_label()?;
+        self.validate_field()?;
+        Ok(())
+    }
+
+    fn validate_label(&self) -> Result<(), ErrorBytes<'a>> {
+        if self.label.iter().all(|&b| is_lbyte(b)) {
+            Ok(())
+        } else {
+            Err(ErrorBytes::invalid_label(self.label)
+                .put_line(self.line)
+                .put_span(self.start, self.start + self.label.len()))
+        }
+    }
+
+    fn validate_field(&self) -> Result<(), ErrorBytes<'a>> {
+        if self.field.iter().all(|&b| is_fbyte(b)) {
+            Ok(())
+        } else {
+            Err(ErrorBytes::invalid_field(self.field)
+                .put_line(self.line)
+                .put_span(self.start + self.label.len() + 1, self.end))
+        }
+    }
+}
+
+pub fn tokenize_bytes<'a>(input: &'a [u8]) -> DataBytes<'a> {
+    DataBytes {
+        done: input.is_empty(),
+        input,
+        current_line: 0,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+    use super::*;
+    use std::{vec, vec::Vec};
+
+    #[test]
+    fn tokenize_bytes_test() {
+        let expected = vec![
+            PairTokenBytes {
+                label: b"mylabel1",
+                field: b"1",
+                line: 0,
+                start: 0,
+                end: 10,
+            },
+            PairTokenBytes {
+                label: b"mylabel2",
+                field: b"testing",
+                line: 0,
+                start: 11,
+                end: 27,
+            },
+            PairTokenBytes {
+                label: b"mylabel3",
+                field: b"1234",
+                line: 0,
+                start: 28,
+                end: 41,
+            },
+        ];
+
+        let data = b"mylabel1:1\tmylabel2:testing\tmylabel3:1234";
+
+        let mut pairs = Vec::new();
+        for line in tokenize_bytes(data) {
+            for pair in line {
+                pairs.push(pair.unwrap());
+            }
+        }
+
+        assert_eq!(pairs, expected)
+    }
+
+    #[test]
+    fn tokenize_bytes_multiline() {
+        let data = b"mylabel:1\nmylabel:2\n";
+
+        let lines: Vec<usize> = tokenize_bytes(data).map(|r| r.current_line).collect();
+
+        assert_eq!(lines, vec![0, 1]);
+    }
+
+    #[test]
+    fn invalid_label_bytes() {
+        let out: Vec<_> = tokenize_bytes(b"!123:testing").next().unwrap().collect();
+        assert_eq!(out, vec![Err(ErrorBytes::invalid_label(b"!123").put_span(0, 4))]);
+    }
+
+    #[test]
+    fn invalid_field_bytes() {
+        let out: Vec<_> = tokenize_bytes(b"mylabel:testing\x00stuff")
+            .next()
+            .unwrap()
+            .collect();
+        assert_eq!(
+            out,
+            vec![Err(ErrorBytes::invalid_field(b"testing\x00stuff").put_span(8, 21))]
+        );
+    }
+
+    #[test]
+    fn invalid_pair_bytes() {
+        let out: Vec<_> = tokenize_bytes(b"mylabel:testing\tstuff")
+            .next()
+            .unwrap()
+            .collect();
+        assert_eq!(out[1], Err(ErrorBytes::invalid_pair(b"stuff").put_span(16, 21)));
+    }
+}
+
+#[cfg(test)]
+mod no_std_test {
+    use super::*;
+
+    #[test]
+    fn tokenize_bytes_test() {
+        let mut pairs: [PairTokenBytes<'_>; 3] = [
+            PairTokenBytes::default(),
+            PairTokenBytes::default(),
+            PairTokenBytes::default(),
+        ];
+
+        let data = b"mylabel1:1\tmylabel2:testing\tmylabel3:1234";
+
+        let mut counter = 0;
+
+        for line in tokenize_bytes(data) {
+            for pair in line.flatten() {
+                pairs[counter] = pair;
+                counter += 1;
+            }
+        }
+
+        assert_eq!(
+            pairs,
+            [
+                PairTokenBytes {
+                    label: b"mylabel1",
+                    field: b"1",
+                    line: 0,
+                    start: 0,
+                    end: 10,
+                },
+                PairTokenBytes {
+                    label: b"mylabel2",
+                    field: b"testing",
+                    line: 0,
+                    start: 11,
+                    end: 27,
+                },
+                PairTokenBytes {
+                    label: b"mylabel3",
+                    field: b"1234",
+                    line: 0,
+                    start: 28,
+                    end: 41,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn tokenize_bytes_matches_str_lines_on_lone_newline() {
+        assert_eq!("\n".lines().count(), 1);
+        assert_eq!(tokenize_bytes(b"\n").count(), 1);
+    }
+
+    #[test]
+    fn tokenize_bytes_empty_input_yields_no_lines() {
+        assert_eq!(tokenize_bytes(b"").count(), 0);
+    }
+
+    #[test]
+    fn invalid_label() {
+        let mut line = tokenize_bytes(b"!123:testing").next().unwrap();
+        let expected = Err(ErrorBytes::invalid_label(b"!123").put_span(0, 4));
+        assert_eq!(expected, line.next().unwrap());
+    }
+
+    #[test]
+    fn invalid_field() {
+        let mut line = tokenize_bytes(b"mylabel:testing\x00stuff").next().unwrap();
+        let expected = Err(ErrorBytes::invalid_field(b"testing\x00stuff").put_span(8, 21));
+        assert_eq!(expected, line.next().unwrap());
+    }
+
+    #[test]
+    fn invalid_pair() {
+        let mut line = tokenize_bytes(b"mylabel:testing\tstuff").next().unwrap();
+        line.next();
+        let expected = Err(ErrorBytes::invalid_pair(b"stuff").put_span(16, 21));
+        assert_eq!(expected, line.next().unwrap());
+    }
+}
@@ -0,0 +1,265 @@
+//! Serde [`Deserializer`] for mapping LTSV records onto Rust structs.
+//!
+//! Each record is treated as a map: a [`PairToken`]'s label is the key and its
+//! field is the value, so `#[derive(Deserialize)]` structs can be filled
+//! straight from `label:field\t...` records without walking [`Record`] by hand.
+//!
+//! Requires both the `serde` and `std` features, since deserializing
+//! collects pairs into an owned `Vec`/`String`.
+
+use crate::{tokenize, Error as LtsvError, PairToken, Record};
+use serde::de::{self, DeserializeSeed, Error as _, IntoDeserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use std::format;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Deserialization errors, either forwarded from the LTSV tokenizer or raised
+/// by serde itself (e.g. a field that does not parse into the target type).
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Ltsv(String),
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Ltsv(msg) => write!(f, "{}", msg),
+            Error::Message(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl<'a> From<LtsvError<'a>> for Error {
+    fn from(err: LtsvError<'a>) -> Error {
+        Error::Ltsv(format!("{:?}", err))
+    }
+}
+
+/// Deserializes every record in `input` into a `Vec<T>`, one `T` per line.
+pub fn from_str<'de, T>(input: &'de str) -> Result<Vec<T>, Error>
+where
+    T: Deserialize<'de>,
+{
+    let mut out = Vec::new();
+
+    for record in tokenize(input) {
+        let mut deserializer = Deserializer::from_record(record)?;
+        out.push(T::deserialize(&mut deserializer)?);
+    }
+
+    Ok(out)
+}
+
+/// Deserializer for a single LTSV record, constructed via [`from_str`].
+pub struct Deserializer<'de> {
+    pairs: Vec<PairToken<'de>>,
+    index: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn from_record(record: Record<'de>) -> Result<Deserializer<'de>, Error> {
+        let pairs = record.collect::<Result<Vec<_>, _>>()?;
+        Ok(Deserializer { pairs, index: 0 })
+    }
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    // a record is always present once tokenized, so there's no null
+    // representation to check for here
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes
+        byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map enum identifier ignored_any
+    }
+}
+
+impl<'de> MapAccess<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        match self.pairs.get(self.index) {
+            Some(pair) => seed.deserialize(pair.label.into_deserializer()).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let field = self.pairs[self.index].field;
+        self.index += 1;
+        seed.deserialize(FieldDeserializer(field))
+    }
+}
+
+/// Deserializes a single field string, coercing it into whatever scalar type
+/// the visitor asks for and falling back to a plain `&str`/`String`.
+struct FieldDeserializer<'de>(&'de str);
+
+macro_rules! deserialize_parsed {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+        where
+            V: Visitor<'de>,
+        {
+            let value: $ty = self
+                .0
+                .parse()
+                .map_err(|_| Error::custom(format!("invalid {}: {:?}", stringify!($ty), self.0)))?;
+            visitor.$visit(value)
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.0)
+    }
+
+    deserialize_parsed!(deserialize_bool, visit_bool, bool);
+    deserialize_parsed!(deserialize_i8, visit_i8, i8);
+    deserialize_parsed!(deserialize_i16, visit_i16, i16);
+    deserialize_parsed!(deserialize_i32, visit_i32, i32);
+    deserialize_parsed!(deserialize_i64, visit_i64, i64);
+    deserialize_parsed!(deserialize_u8, visit_u8, u8);
+    deserialize_parsed!(deserialize_u16, visit_u16, u16);
+    deserialize_parsed!(deserialize_u32, visit_u32, u32);
+    deserialize_parsed!(deserialize_u64, visit_u64, u64);
+    deserialize_parsed!(deserialize_f32, visit_f32, f32);
+    deserialize_parsed!(deserialize_f64, visit_f64, f64);
+
+    // an empty field is read as `None`, the same way `ser` writes `None`
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        char bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod de_test {
+    use super::*;
+    use serde::Deserialize;
+    use std::vec;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Rec<'a> {
+        host: &'a str,
+        status: u32,
+        retries: Option<u32>,
+    }
+
+    #[test]
+    fn deserializes_a_typical_struct() {
+        let recs: Vec<Rec> = from_str("host:127.0.0.1\tstatus:200\tretries:3").unwrap();
+
+        assert_eq!(
+            recs,
+            vec![Rec {
+                host: "127.0.0.1",
+                status: 200,
+                retries: Some(3),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_required_field_errors() {
+        let out: Result<Vec<Rec>, Error> = from_str("host:127.0.0.1\tretries:3");
+
+        assert!(out.is_err());
+    }
+
+    #[test]
+    fn unparseable_numeric_field_errors() {
+        let out: Result<Vec<Rec>, Error> = from_str("host:127.0.0.1\tstatus:not-a-number");
+
+        assert!(out.is_err());
+    }
+
+    #[test]
+    fn option_field_present_is_some() {
+        let recs: Vec<Rec> = from_str("host:127.0.0.1\tstatus:200\tretries:3").unwrap();
+
+        assert_eq!(recs[0].retries, Some(3));
+    }
+
+    #[test]
+    fn option_field_absent_is_none() {
+        let recs: Vec<Rec> = from_str("host:127.0.0.1\tstatus:200").unwrap();
+
+        assert_eq!(recs[0].retries, None);
+    }
+}
@@ -51,6 +51,10 @@
 //! ```
 //!
 //! Side note: This is not unicode aware, but I followed the original grammar when implement this
+//!
+//! The `fbyte` grammar rule allows any byte up to `0xFF`, which the `&str` API above can't
+//! represent since it relies on UTF-8 aware `str` methods. For that case, see the
+//! [`bytes`] module, which parses `&[u8]` directly.
 
 // grammar:
 //
@@ -72,11 +76,19 @@ extern crate std;
 #[cfg(feature = "std")]
 use std::vec::Vec;
 
+pub mod bytes;
+// `de`/`ser` build on `Vec`/`String`, so `serde` alone isn't enough; `std` is
+// required too.
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod de;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod ser;
+
 pub const NEWLINE: char = '\n';
 pub const TAB: char = '\t';
 pub const SPLITTER: char = ':';
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ErrorKind {
     InvalidPair,
     InvalidLabel,
@@ -196,29 +208,32 @@ impl<'a> Iterator for Record<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let pair = self.fields.next()?;
+        let start = self.current_pointer;
         // start + byte length of the field pair
-        let end = self.current_pointer + pair.len();
+        let end = start + pair.len();
+
+        // skip the tab character, even on an invalid pair, so a bad field
+        // does not throw off the spans of the ones that follow it
+        self.current_pointer = end + 1;
 
         if let Some((label, field)) = pair.split_once(SPLITTER) {
             let pair = PairToken {
                 label,
                 field,
                 line: self.current_line,
-                start: self.current_pointer,
-                end: end,
+                start,
+                end,
             };
+
             if let Err(e) = pair.validate() {
                 return Some(Err(e));
             };
 
-            // skip the tab character
-            self.current_pointer = end + 1;
-
             return Some(Ok(pair));
         } else {
             return Some(Err(Error::invalid_pair(pair)
                 .put_line(self.current_line)
-                .put_span(self.current_pointer, end)));
+                .put_span(start, end)));
         };
     }
 }
@@ -269,15 +284,7 @@ impl<'a> PairToken<'a> {
     }
 
     fn validate_label(&self) -> Result<(), Error<'a>> {
-        if self.label.as_bytes().iter().all(|b| match b {
-            0x30..=0x39 => true,
-            0x41..=0x5a => true,
-            0x61..=0x7a => true,
-            b'_' => true,
-            b'.' => true,
-            b'-' => true,
-            _ => false,
-        }) {
+        if validate_label(self.label) {
             Ok(())
         } else {
             Err(Error::invalid_label(self.label)
@@ -287,13 +294,7 @@ impl<'a> PairToken<'a> {
     }
 
     fn validate_field(&self) -> Result<(), Error<'a>> {
-        if self.field.as_bytes().iter().all(|b| match b {
-            0x01..=0x08 => true,
-            0x0b => true,
-            0x0c => true,
-            0x0e..=0xff => true,
-            _ => false,
-        }) {
+        if self.field.as_bytes().iter().all(|&b| is_fbyte(b)) {
             Ok(())
         } else {
             Err(Error::invalid_field(self.field)
@@ -303,6 +304,21 @@ impl<'a> PairToken<'a> {
     }
 }
 
+/// Returns `true` if `b` is a valid `lbyte` per the grammar: `%x30-39 / %x41-5A / %x61-7A / "_" / "." / "-"`.
+pub(crate) fn is_lbyte(b: u8) -> bool {
+    matches!(b, 0x30..=0x39 | 0x41..=0x5a | 0x61..=0x7a | b'_' | b'.' | b'-')
+}
+
+/// Returns `true` if `b` is a valid `fbyte` per the grammar: `%x01-08 / %x0B / %x0C / %x0E-FF`.
+pub(crate) fn is_fbyte(b: u8) -> bool {
+    matches!(b, 0x01..=0x08 | 0x0b | 0x0c | 0x0e..=0xff)
+}
+
+/// Returns `true` if every byte in `label` is a valid `lbyte` per the grammar.
+pub fn validate_label(label: &str) -> bool {
+    label.as_bytes().iter().all(|&b| is_lbyte(b))
+}
+
 pub fn tokenize<'a>(input: &'a str) -> Data<'a> {
     Data {
         lines: input.lines(),
@@ -320,6 +336,29 @@ pub fn validate<'a>(input: &'a str) -> Result<(), Error<'a>> {
     Ok(())
 }
 
+/// Like [`validate`], but does not stop at the first bad field or record.
+/// Every diagnostic is collected instead, so a log-ingestion caller can see
+/// every problem in the input in one pass rather than fixing and re-running
+/// one error at a time.
+#[cfg(feature = "std")]
+pub fn validate_all<'a>(input: &'a str) -> Result<(), Vec<Error<'a>>> {
+    let mut errors = Vec::new();
+
+    for line in tokenize(input) {
+        for field in line {
+            if let Err(e) = field {
+                errors.push(e);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 #[cfg(feature = "std")]
 pub fn parse<'a>(input: &'a str) -> Result<Vec<Vec<Pair<'a>>>, Error<'a>> {
     let mut out = Vec::new();
@@ -337,6 +376,31 @@ fn pair_from<'a>(token: Result<PairToken<'a>, Error<'a>>) -> Result<Pair<'a>, Er
     Ok(Pair::from(token?))
 }
 
+/// Like [`parse`], but does not stop at the first bad field or record.
+/// Valid pairs are still collected per line, and every diagnostic from an
+/// invalid field or record is returned alongside them, so one malformed line
+/// does not hide the valid data that comes after it.
+#[cfg(feature = "std")]
+pub fn parse_all<'a>(input: &'a str) -> (Vec<Vec<Pair<'a>>>, Vec<Error<'a>>) {
+    let mut out = Vec::new();
+    let mut errors = Vec::new();
+
+    for line in tokenize(input) {
+        let mut fields = Vec::new();
+
+        for field in line {
+            match field {
+                Ok(pair) => fields.push(Pair::from(pair)),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        out.push(fields);
+    }
+
+    (out, errors)
+}
+
 #[cfg(all(test, feature = "std"))]
 mod std_test {
     use super::*;
@@ -415,6 +479,43 @@ mod std_test {
         assert_eq!(fields, expected)
     }
 
+    #[test]
+    fn validate_all_collects_every_error() {
+        let out = validate_all("!123:testing\tmylabel:testing\ttest\nmore:data\tbad");
+
+        let errors = out.unwrap_err();
+        assert_eq!(
+            vec![ErrorKind::InvalidLabel, ErrorKind::InvalidPair, ErrorKind::InvalidPair],
+            errors.iter().map(|e| e.kind).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn validate_all_ok_when_valid() {
+        assert_eq!(Ok(()), validate_all("mylabel:testing\tmore:data"));
+    }
+
+    #[test]
+    fn parse_all_keeps_valid_pairs_alongside_errors() {
+        let (out, errors) = parse_all("mylabel:testing\ttest\tmore:data");
+
+        assert_eq!(
+            vec![vec![
+                Pair {
+                    label: "mylabel",
+                    field: "testing"
+                },
+                Pair {
+                    label: "more",
+                    field: "data"
+                },
+            ]],
+            out
+        );
+        assert_eq!(1, errors.len());
+        assert_eq!(ErrorKind::InvalidPair, errors[0].kind);
+    }
+
     #[test]
     fn tokenize_test() {
         let expected = vec![
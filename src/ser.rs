@@ -0,0 +1,435 @@
+//! Serde [`Serializer`] for emitting LTSV records from Rust structs.
+//!
+//! Only struct serialization is supported: each field becomes a
+//! `label:field` pair, joined with [`crate::TAB`], and a key that fails
+//! [`crate::validate_label`] is rejected rather than silently emitted.
+//!
+//! Requires both the `serde` and `std` features, since the output record is
+//! built up in an owned `String`.
+
+use crate::{is_fbyte, validate_label, SPLITTER, TAB};
+use serde::ser::{self, Error as _, Serialize};
+use std::fmt;
+use std::string::{String, ToString};
+
+/// Serialization errors: either an invalid label, an invalid field value, or
+/// a value serde can't turn into a single LTSV field (anything that isn't a
+/// scalar).
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    InvalidLabel(String),
+    InvalidField(String),
+    Message(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidLabel(label) => write!(f, "invalid label: {:?}", label),
+            Error::InvalidField(field) => write!(f, "invalid field: {:?}", field),
+            Error::Message(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+/// Serializes `value` into a single `label:field\t...` record.
+pub fn to_string<T: Serialize>(value: &T) -> Result<String, Error> {
+    let mut serializer = Serializer {
+        output: String::new(),
+    };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+pub struct Serializer {
+    output: String,
+}
+
+impl<'a> ser::Serializer for &'a mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = StructSerializer<'a>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Ok(StructSerializer {
+            ser: self,
+            first: true,
+        })
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_i8(self, _v: i8) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_i16(self, _v: i16) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_i32(self, _v: i32) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_i64(self, _v: i64) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_u8(self, _v: u8) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_u16(self, _v: u16) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_u32(self, _v: u32) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_u64(self, _v: u64) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_f32(self, _v: f32) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_f64(self, _v: f64) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_char(self, _v: char) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_str(self, _v: &str) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_none(self) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _v: &T) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::custom("only structs can be serialized to LTSV"))
+    }
+}
+
+pub struct StructSerializer<'a> {
+    ser: &'a mut Serializer,
+    first: bool,
+}
+
+impl<'a> ser::SerializeStruct for StructSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        if !validate_label(key) {
+            return Err(Error::InvalidLabel(key.to_string()));
+        }
+
+        if !self.first {
+            self.ser.output.push(TAB);
+        }
+        self.first = false;
+
+        self.ser.output.push_str(key);
+        self.ser.output.push(SPLITTER);
+        value.serialize(FieldSerializer {
+            output: &mut self.ser.output,
+        })
+    }
+
+    fn end(self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+/// Serializer for a single field value: formats a scalar directly into the
+/// record buffer, the mirror image of `de::FieldDeserializer`'s parsing.
+struct FieldSerializer<'a> {
+    output: &'a mut String,
+}
+
+/// Rejects a field value that would corrupt the record framing, mirroring
+/// the label check in `StructSerializer::serialize_field`.
+fn validate_field(v: &str) -> Result<(), Error> {
+    if v.as_bytes().iter().all(|&b| is_fbyte(b)) {
+        Ok(())
+    } else {
+        Err(Error::InvalidField(v.to_string()))
+    }
+}
+
+macro_rules! serialize_display {
+    ($method:ident, $ty:ty) => {
+        fn $method(self, v: $ty) -> Result<(), Error> {
+            self.output.push_str(&v.to_string());
+            Ok(())
+        }
+    };
+}
+
+impl<'a> ser::Serializer for FieldSerializer<'a> {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = ser::Impossible<(), Error>;
+    type SerializeTuple = ser::Impossible<(), Error>;
+    type SerializeTupleStruct = ser::Impossible<(), Error>;
+    type SerializeTupleVariant = ser::Impossible<(), Error>;
+    type SerializeMap = ser::Impossible<(), Error>;
+    type SerializeStruct = ser::Impossible<(), Error>;
+    type SerializeStructVariant = ser::Impossible<(), Error>;
+
+    serialize_display!(serialize_bool, bool);
+    serialize_display!(serialize_i8, i8);
+    serialize_display!(serialize_i16, i16);
+    serialize_display!(serialize_i32, i32);
+    serialize_display!(serialize_i64, i64);
+    serialize_display!(serialize_u8, u8);
+    serialize_display!(serialize_u16, u16);
+    serialize_display!(serialize_u32, u32);
+    serialize_display!(serialize_u64, u64);
+    serialize_display!(serialize_f32, f32);
+    serialize_display!(serialize_f64, f64);
+
+    fn serialize_char(self, v: char) -> Result<(), Error> {
+        let mut buf = [0u8; 4];
+        let s = v.encode_utf8(&mut buf);
+        validate_field(s)?;
+        self.output.push_str(s);
+        Ok(())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), Error> {
+        validate_field(v)?;
+        self.output.push_str(v);
+        Ok(())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_none(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Error> {
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), Error> {
+        self.output.push_str(variant);
+        Ok(())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Error> {
+        Err(Error::custom("field values must be scalars or strings"))
+    }
+}
+
+#[cfg(test)]
+mod ser_test {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Rec<'a> {
+        host: &'a str,
+        status: u32,
+        retries: Option<u32>,
+    }
+
+    #[test]
+    fn serializes_a_typical_struct() {
+        let out = to_string(&Rec {
+            host: "127.0.0.1",
+            status: 200,
+            retries: Some(3),
+        })
+        .unwrap();
+
+        assert_eq!(out, "host:127.0.0.1\tstatus:200\tretries:3");
+    }
+
+    #[test]
+    fn none_option_field_serializes_to_an_empty_field() {
+        let out = to_string(&Rec {
+            host: "127.0.0.1",
+            status: 200,
+            retries: None,
+        })
+        .unwrap();
+
+        assert_eq!(out, "host:127.0.0.1\tstatus:200\tretries:");
+    }
+
+    #[test]
+    fn invalid_label_errors() {
+        #[derive(Serialize)]
+        struct BadLabel {
+            #[serde(rename = "!bad")]
+            field: u32,
+        }
+
+        let out = to_string(&BadLabel { field: 1 });
+
+        assert_eq!(out, Err(Error::InvalidLabel("!bad".to_string())));
+    }
+
+    #[test]
+    fn field_value_containing_a_tab_errors() {
+        #[derive(Serialize)]
+        struct WithTab<'a> {
+            msg: &'a str,
+        }
+
+        let out = to_string(&WithTab {
+            msg: "hello\tworld",
+        });
+
+        assert_eq!(out, Err(Error::InvalidField("hello\tworld".to_string())));
+    }
+}